@@ -1,14 +1,28 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
 use log;
 
-use super::{font_set::FontSet, screen::Screen};
+use super::{
+    debugger::{DebugCommand, Debugger},
+    font_set::FontSet,
+    instruction::{decode, Instruction},
+    keypad::{Hotkey, KeyState, Keypad, NUM_KEYS},
+    quirks::{IndexIncrement, Quirks},
+    screen::Screen,
+    sound::Sound,
+    timing::Timing,
+};
 use rand::random;
 
 const RAM_SIZE: usize = 4048;
 const START_ADDR: usize = 0x200;
 const NUM_REGS: usize = 16;
 const STACK_SIZE: usize = 16;
-const NUM_KEYS: usize = 16;
-const TICKS_PER_FRAME: usize = 10;
 
 struct Stack {
     data: [usize; STACK_SIZE],
@@ -41,13 +55,25 @@ pub struct CPU {
     i_reg: u16,
     dt: u8,
     st: u8,
-    keys: [bool; NUM_KEYS],
+    keys: KeyState,
+    keys_prev: KeyState,
     stack: Stack,
     screen: Screen,
+    keypad: Box<dyn Keypad>,
+    quirks: Quirks,
+    state_path: Option<String>,
+    debugger: Option<Debugger>,
+    sound: Box<dyn Sound>,
+    timing: Timing,
 }
 
 impl CPU {
-    pub fn new(screen: Screen) -> Self {
+    pub fn new(
+        screen: Screen,
+        keypad: Box<dyn Keypad>,
+        quirks: Quirks,
+        sound: Box<dyn Sound>,
+    ) -> Self {
         let mut cpu = Self {
             v_reg: [0; NUM_REGS],
             ram: [0; RAM_SIZE],
@@ -56,16 +82,45 @@ impl CPU {
             dt: 0,
             st: 0,
             keys: [false; NUM_KEYS],
+            keys_prev: [false; NUM_KEYS],
             stack: Stack::new(),
             screen,
+            keypad,
+            quirks,
+            state_path: None,
+            debugger: None,
+            sound,
+            timing: Timing::default(),
         };
         cpu.preload_fonts();
 
         cpu
     }
 
-    pub fn with_rom(screen: Screen, filename: &str) -> Self {
-        let mut cpu = Self::new(screen);
+    /// Set the path used by the save-state/load-state hotkeys.
+    pub fn set_state_path(&mut self, path: String) {
+        self.state_path = Some(path);
+    }
+
+    /// Override the default ~540 ips / 60 fps timing.
+    pub fn set_timing(&mut self, timing: Timing) {
+        self.timing = timing;
+    }
+
+    /// Turn on the interactive single-step debugger, stopped at the first
+    /// instruction.
+    pub fn enable_debugger(&mut self) {
+        self.debugger = Some(Debugger::new());
+    }
+
+    pub fn with_rom(
+        screen: Screen,
+        keypad: Box<dyn Keypad>,
+        quirks: Quirks,
+        sound: Box<dyn Sound>,
+        filename: &str,
+    ) -> Self {
+        let mut cpu = Self::new(screen, keypad, quirks, sound);
         let buf: Vec<u8> = std::fs::read(filename).unwrap();
         log::debug!("loaded rom {}, {} bytes", filename, buf.len());
         cpu.ram[START_ADDR..START_ADDR + buf.len()].clone_from_slice(&buf);
@@ -76,33 +131,158 @@ impl CPU {
         self.ram[..FontSet::FONTSET_SIZE].copy_from_slice(&FontSet::FONTSET);
     }
 
+    /// Run forever, holding `timing.fps` by sleeping out whatever time a
+    /// frame's instructions and render didn't use. The delay/sound timers
+    /// tick once per frame, so at the default 60 fps they decrement at the
+    /// real 60 Hz regardless of `timing.clock_hz`.
     pub fn run(&mut self) {
+        let frame_duration = Duration::from_secs_f64(1.0 / self.timing.fps as f64);
+
         loop {
+            let frame_start = Instant::now();
+
             self.run_single_frame();
+
+            if let Some(remaining) = frame_duration.checked_sub(frame_start.elapsed()) {
+                thread::sleep(remaining);
+            }
         }
     }
 
-    pub fn run_n_ticks(&mut self, ticks: usize) {
-        for _ in 0..ticks {
+    pub fn run_n_ticks(&mut self, frames: usize) {
+        for _ in 0..frames {
             self.run_single_frame();
         }
     }
 
     fn run_single_frame(&mut self) {
-        for _ in 0..TICKS_PER_FRAME {
+        self.poll_keys();
+        for _ in 0..self.timing.ticks_per_frame() {
             self.tick();
         }
         self.screen.render();
         self.tick_timers();
     }
 
+    fn poll_keys(&mut self) {
+        self.keys_prev = self.keys;
+        self.keys = self.keypad.poll();
+
+        if let Some(hotkey) = self.keypad.hotkey() {
+            self.handle_hotkey(hotkey);
+        }
+    }
+
+    fn handle_hotkey(&mut self, hotkey: Hotkey) {
+        let Some(path) = self.state_path.clone() else {
+            return;
+        };
+        let result = match hotkey {
+            Hotkey::SaveState => self.save_state(&path),
+            Hotkey::LoadState => self.load_state(&path),
+        };
+        if let Err(err) = result {
+            log::debug!("state file {path}: {err}");
+        }
+    }
+
     pub fn tick(&mut self) {
+        self.maybe_debug_break();
+
         //log::debug!("tick with pc: {}", self.pc);
-        let high_byte = self.ram[self.pc] as u16;
-        let low_byte = self.ram[self.pc + 1] as u16;
+        let high_byte = self.ram[self.pc];
+        let low_byte = self.ram[self.pc + 1];
         self.pc += 2;
 
-        self.dispatch(high_byte, low_byte);
+        self.dispatch(decode(high_byte, low_byte));
+
+        if let Some(debugger) = self.debugger.as_mut() {
+            debugger.tick();
+        }
+    }
+
+    /// If the debugger is enabled and stopped (or a breakpoint was hit),
+    /// block on stdin and dispatch monitor commands until told to step or
+    /// continue.
+    fn maybe_debug_break(&mut self) {
+        let Some(mut debugger) = self.debugger.take() else {
+            return;
+        };
+
+        while debugger.should_break(self.pc) {
+            match debugger.next_command() {
+                DebugCommand::Step(n) => debugger.step(n),
+                DebugCommand::Continue => debugger.resume(),
+                DebugCommand::SetBreakpoint(addr) => {
+                    debugger.add_breakpoint(addr);
+                    continue;
+                }
+                DebugCommand::DumpRegisters => {
+                    self.print_registers();
+                    continue;
+                }
+                DebugCommand::DumpMemory(addr, len) => {
+                    self.print_memory(addr, len);
+                    continue;
+                }
+                DebugCommand::Disassemble(addr, count) => {
+                    self.print_disassembly(addr, count);
+                    continue;
+                }
+                DebugCommand::Invalid(line) => {
+                    println!("unrecognized command: {line}");
+                    continue;
+                }
+            }
+            break;
+        }
+
+        self.debugger = Some(debugger);
+    }
+
+    fn print_registers(&self) {
+        println!(
+            "pc={:#05X} i={:#05X} dt={:#04X} st={:#04X}",
+            self.pc, self.i_reg, self.dt, self.st
+        );
+        for (reg, value) in self.v_reg.iter().enumerate() {
+            println!("v{reg:X}={value:#04X}");
+        }
+        println!(
+            "sp={} stack={:?}",
+            self.stack.sp,
+            &self.stack.data[..self.stack.sp]
+        );
+    }
+
+    fn print_memory(&self, addr: usize, len: usize) {
+        let Some(end) = addr.checked_add(len).filter(|&end| end <= RAM_SIZE) else {
+            println!("address range {addr:#05X}..+{len:#X} is outside RAM ({RAM_SIZE:#05X} bytes)");
+            return;
+        };
+
+        for (row, chunk) in self.ram[addr..end].chunks(16).enumerate() {
+            let bytes: Vec<String> = chunk.iter().map(|byte| format!("{byte:02X}")).collect();
+            println!("{:#05X}: {}", addr + row * 16, bytes.join(" "));
+        }
+    }
+
+    fn print_disassembly(&self, addr: usize, count: usize) {
+        let end = count
+            .checked_mul(2)
+            .and_then(|bytes| addr.checked_add(bytes))
+            .filter(|&end| end <= RAM_SIZE);
+        let Some(end) = end else {
+            println!("address range {addr:#05X}..+{count} instructions is outside RAM ({RAM_SIZE:#05X} bytes)");
+            return;
+        };
+
+        let mut pc = addr;
+        while pc < end {
+            let instruction = decode(self.ram[pc], self.ram[pc + 1]);
+            println!("{pc:#05X}: {instruction}");
+            pc += 2;
+        }
     }
 
     pub fn tick_timers(&mut self) {
@@ -110,72 +290,63 @@ impl CPU {
             self.dt -= 1;
         }
         if self.st > 0 {
-            if self.st == 1 {
-                // emit sound
-                //log::debug!("Sound not implemented");
-                print!(r"\a");
-            }
-            self.st -= 1;
-        }
-    }
-
-    fn dispatch(&mut self, high_byte: u16, low_byte: u16) {
-        let b3 = ((high_byte & 0xF0) >> 4) as u8;
-        let b2 = (high_byte & 0x0F) as u8;
-        let b1 = ((low_byte & 0xF0) >> 4) as u8;
-        let b0 = (low_byte & 0x0F) as u8;
-        let nn: u16 = low_byte & 0xFF;
-        let nnn: u16 = ((high_byte << 8) | low_byte) & 0xFFF;
-
-        match b3 {
-            0 => match (b2, b1, b0) {
-                (0, 0xE, 0xE) => self.oc_00ee(),
-                (0, 0xE, 0) => self.oc_00e0(),
-                (0, 0, 0) => self.oc_0000(),
-                _ => unreachable!(),
-            },
-            1 => self.oc_1nnn(nnn),
-            2 => self.oc_2nnn(nnn),
-            3 => self.oc_3xnn(b2, nn),
-            4 => self.oc_4xnn(b2, nn),
-            5 if b0 == 0 => self.oc_5xy0(b2, b1),
-            6 => self.oc_6xnn(b2, nn),
-            7 => self.oc_7xnn(b2, nn),
-            8 => match b0 {
-                0 => self.oc_8xy0(b2, b1),
-                1 => self.oc_8xy1(b2, b1),
-                2 => self.oc_8xy2(b2, b1),
-                3 => self.oc_8xy3(b2, b1),
-                4 => self.oc_8xy4(b2, b1),
-                5 => self.oc_8xy5(b2, b1),
-                6 => self.oc_8xy6(b2, b1),
-                7 => self.oc_8xy7(b2, b1),
-                0xE => self.oc_8xye(b2, b1),
-                _ => unreachable!(),
-            },
-            9 if b0 == 0 => self.oc_9xy0(b2, b1),
-            0xA => self.oc_annn(nnn),
-            0xB => self.oc_bnnn(nnn),
-            0xC => self.oc_cxnn(b2, nn),
-            0xD => self.oc_dxyn(b2, b1, b0),
-            0xE => match (b1, b0) {
-                (9, 0xE) => self.oc_ex9e(b2),
-                (0xA, 1) => self.oc_exa1(b2),
-                _ => unreachable!(),
-            },
-            0xF => match (b1, b0) {
-                (0, 7) => self.oc_fx07(b2),
-                (0, 8) => self.oc_fx08(b2),
-                (1, 5) => self.oc_fx15(b2),
-                (1, 8) => self.oc_fx18(b2),
-                (1, 0xE) => self.oc_fx1e(b2),
-                (2, 9) => self.oc_fx29(b2),
-                (3, 3) => self.oc_fx33(b2),
-                (5, 5) => self.oc_fx55(b2),
-                (6, 5) => self.oc_fx65(b2),
-                _ => unreachable!(),
-            },
-            _ => panic!("unsupported opcode {}{}{}{}", b3, b2, b1, b0),
+            self.set_sound_timer(self.st - 1);
+        }
+    }
+
+    /// Set the sound timer, starting or stopping the beep as it crosses
+    /// zero. Shared by `tick_timers` counting down and `oc_fx18` setting it
+    /// directly, so the beep reacts immediately either way.
+    fn set_sound_timer(&mut self, value: u8) {
+        let was_playing = self.st > 0;
+        self.st = value;
+        let is_playing = self.st > 0;
+
+        if is_playing && !was_playing {
+            self.sound.start();
+        } else if was_playing && !is_playing {
+            self.sound.stop();
+        }
+    }
+
+    fn dispatch(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::NoOp => self.oc_0000(),
+            Instruction::ClearScreen => self.oc_00e0(),
+            Instruction::Return => self.oc_00ee(),
+            Instruction::Jump(nnn) => self.oc_1nnn(nnn),
+            Instruction::CallSubroutine(nnn) => self.oc_2nnn(nnn),
+            Instruction::SkipEqImm(x, nn) => self.oc_3xnn(x, nn as u16),
+            Instruction::SkipNeqImm(x, nn) => self.oc_4xnn(x, nn as u16),
+            Instruction::SkipEqReg(x, y) => self.oc_5xy0(x, y),
+            Instruction::SetImm(x, nn) => self.oc_6xnn(x, nn as u16),
+            Instruction::AddImm(x, nn) => self.oc_7xnn(x, nn as u16),
+            Instruction::SetReg(x, y) => self.oc_8xy0(x, y),
+            Instruction::Or(x, y) => self.oc_8xy1(x, y),
+            Instruction::And(x, y) => self.oc_8xy2(x, y),
+            Instruction::Xor(x, y) => self.oc_8xy3(x, y),
+            Instruction::AddReg(x, y) => self.oc_8xy4(x, y),
+            Instruction::SubReg(x, y) => self.oc_8xy5(x, y),
+            Instruction::ShiftRight(x, y) => self.oc_8xy6(x, y),
+            Instruction::SubRegRev(x, y) => self.oc_8xy7(x, y),
+            Instruction::ShiftLeft(x, y) => self.oc_8xye(x, y),
+            Instruction::SkipNeqReg(x, y) => self.oc_9xy0(x, y),
+            Instruction::SetIndex(nnn) => self.oc_annn(nnn),
+            Instruction::JumpOffset(x, nnn) => self.oc_bnnn(x, nnn),
+            Instruction::Random(x, nn) => self.oc_cxnn(x, nn as u16),
+            Instruction::Draw(x, y, n) => self.oc_dxyn(x, y, n),
+            Instruction::SkipKeyPressed(x) => self.oc_ex9e(x),
+            Instruction::SkipKeyNotPressed(x) => self.oc_exa1(x),
+            Instruction::GetDelay(x) => self.oc_fx07(x),
+            Instruction::WaitKey(x) => self.oc_fx0a(x),
+            Instruction::SetDelay(x) => self.oc_fx15(x),
+            Instruction::SetSound(x) => self.oc_fx18(x),
+            Instruction::AddIndex(x) => self.oc_fx1e(x),
+            Instruction::SetFont(x) => self.oc_fx29(x),
+            Instruction::StoreBcd(x) => self.oc_fx33(x),
+            Instruction::StoreRegs(x) => self.oc_fx55(x),
+            Instruction::LoadRegs(x) => self.oc_fx65(x),
+            Instruction::Unknown(high, low) => panic!("unsupported opcode {high:02X}{low:02X}"),
         }
     }
 
@@ -267,6 +438,7 @@ impl CPU {
         let vx = self.v_reg[x as usize];
         let vy = self.v_reg[y as usize];
         self.v_reg[x as usize] = vx | vy;
+        self.reset_vf_if_quirked();
     }
 
     // Set VX to VX AND VY
@@ -275,6 +447,7 @@ impl CPU {
         let vx = self.v_reg[x as usize];
         let vy = self.v_reg[y as usize];
         self.v_reg[x as usize] = vx & vy;
+        self.reset_vf_if_quirked();
     }
 
     // Set VX to VX XOR VY
@@ -283,6 +456,13 @@ impl CPU {
         let vx = self.v_reg[x as usize];
         let vy = self.v_reg[y as usize];
         self.v_reg[x as usize] = vx ^ vy;
+        self.reset_vf_if_quirked();
+    }
+
+    fn reset_vf_if_quirked(&mut self) {
+        if self.quirks.logical_resets_vf {
+            self.v_reg[0xF] = 0;
+        }
     }
 
     // Add the value of register VY to register VX
@@ -295,6 +475,9 @@ impl CPU {
         let (sum, over) = vx.overflowing_add(vy);
         log::debug!("oc_8xy4, {}+{}={}, overflow: {}", vx, vy, sum, over);
         let vf = if over { 1 } else { 0 };
+        // VX must be written before VF in this whole 8XY4/8XY5/8XY7 family,
+        // since X can be 0xF and the result write would otherwise clobber the
+        // flag.
         self.v_reg[x as usize] = sum;
         self.v_reg[0xF] = vf;
     }
@@ -312,14 +495,15 @@ impl CPU {
         self.v_reg[0xF] = vf;
     }
 
-    // Store the value of register VY shifted right one bit in register VX
-    // Set register VF to the least significant bit prior to the shift
-    // VY is unchanged
+    // Store the value of register VY (or VX, under the shift-in-place quirk)
+    // shifted right one bit in register VX.
+    // Set register VF to the least significant bit prior to the shift.
     fn oc_8xy6(&mut self, x: u8, y: u8) {
         log::debug!("exec oc_8XY6");
-        let vy = self.v_reg[y as usize];
-        let lsb = vy & 1;
-        self.v_reg[x as usize] = vy >> 1;
+        let src = if self.quirks.shift_in_place { x } else { y };
+        let val = self.v_reg[src as usize];
+        let lsb = val & 1;
+        self.v_reg[x as usize] = val >> 1;
         self.v_reg[0xF] = lsb;
     }
 
@@ -336,14 +520,15 @@ impl CPU {
         self.v_reg[0xF] = vf;
     }
 
-    // Store the value of register VY shifted left one bit in register VX¹
-    // Set register VF to the most significant bit prior to the shift
-    // VY is unchanged
+    // Store the value of register VY (or VX, under the shift-in-place quirk)
+    // shifted left one bit in register VX.
+    // Set register VF to the most significant bit prior to the shift.
     fn oc_8xye(&mut self, x: u8, y: u8) {
         log::debug!("exec oc_8XYE");
-        let vy = self.v_reg[y as usize];
-        let msb = (vy >> 7) & 1;
-        self.v_reg[x as usize] = vy << 1;
+        let src = if self.quirks.shift_in_place { x } else { y };
+        let val = self.v_reg[src as usize];
+        let msb = (val >> 7) & 1;
+        self.v_reg[x as usize] = val << 1;
         self.v_reg[0xF] = msb;
     }
 
@@ -362,10 +547,11 @@ impl CPU {
         self.i_reg = nnn;
     }
 
-    // Jump to address NNN + V0
-    fn oc_bnnn(&mut self, nnn: u16) {
+    // Jump to address NNN + V0 (or NNN + VX, under the jump-adds-vx quirk)
+    fn oc_bnnn(&mut self, x: u8, nnn: u16) {
         log::debug!("exec oc_BNNN");
-        self.pc = (nnn + self.v_reg[0] as u16) as usize;
+        let reg = if self.quirks.jump_adds_vx { x } else { 0 };
+        self.pc = (nnn + self.v_reg[reg as usize] as u16) as usize;
     }
 
     // Set VX to a random number with a mask of NN
@@ -414,10 +600,19 @@ impl CPU {
         self.v_reg[x as usize] = self.dt;
     }
 
-    // Wait for a keypress and store the result in register VX
-    fn oc_fx08(&mut self, _x: u8) {
-        log::debug!("exec oc_fx08");
-        unimplemented!("Keypress nit implemented");
+    // Wait for a keypress and store the result in register VX.
+    // Blocks by re-running this same instruction on every tick until a key
+    // transitions from released to pressed between two keypad polls.
+    fn oc_fx0a(&mut self, x: u8) {
+        log::debug!("exec oc_FX0A");
+        match self.newly_pressed_key() {
+            Some(key) => self.v_reg[x as usize] = key as u8,
+            None => self.pc -= 2,
+        }
+    }
+
+    fn newly_pressed_key(&self) -> Option<usize> {
+        (0..NUM_KEYS).find(|&key| self.keys[key] && !self.keys_prev[key])
     }
 
     // Set the delay timer to the value of register VX
@@ -429,7 +624,7 @@ impl CPU {
     // Set the sound timer to the value of register VX
     fn oc_fx18(&mut self, x: u8) {
         log::debug!("exec oc_fx18");
-        self.st = self.v_reg[x as usize];
+        self.set_sound_timer(self.v_reg[x as usize]);
     }
 
     // Add the value stored in register VX to register I
@@ -462,22 +657,163 @@ impl CPU {
     }
 
     // Store the values of registers V0 to VX inclusive in memory starting at address I
-    // I is set to I + X + 1 after operation²
+    // I is advanced afterwards per the configured load-store-increment quirk
     fn oc_fx55(&mut self, x: u8) {
         log::debug!("exec oc_fx55");
         for idx in 0..=x {
             self.ram[(self.i_reg + idx as u16) as usize] = self.v_reg[idx as usize];
         }
-        self.i_reg += (x + 1) as u16;
+        self.i_reg = self.advance_i_after_load_store(x);
     }
 
     // Fill registers V0 to VX inclusive with the values stored in memory starting at address I
-    // I is set to I + X + 1 after operation²
+    // I is advanced afterwards per the configured load-store-increment quirk
     fn oc_fx65(&mut self, x: u8) {
         log::debug!("exec oc_fx65");
         for idx in 0..=x {
             self.v_reg[idx as usize] = self.ram[(self.i_reg + idx as u16) as usize];
         }
-        self.i_reg += (x + 1) as u16;
+        self.i_reg = self.advance_i_after_load_store(x);
+    }
+
+    fn advance_i_after_load_store(&self, x: u8) -> u16 {
+        match self.quirks.load_store_increment {
+            IndexIncrement::XPlusOne => self.i_reg + (x + 1) as u16,
+            IndexIncrement::Unchanged => self.i_reg,
+            IndexIncrement::X => self.i_reg + x as u16,
+        }
+    }
+
+    /// Dump the complete machine state to `path`: `v_reg`, `ram`, `pc` (u32),
+    /// `i_reg` (u16), `dt`, `st`, `keys`, the stack pointer (u32) followed by
+    /// all `STACK_SIZE` stack slots (u32 each), then the screen's pixel
+    /// buffer - all back to back with no header, in the order read by
+    /// `load_state`. The renderer behind `screen` is never part of the dump,
+    /// only its pixel buffer is.
+    pub fn save_state(&self, path: &str) -> io::Result<()> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&self.v_reg);
+        buf.extend_from_slice(&self.ram);
+        buf.extend_from_slice(&(self.pc as u32).to_le_bytes());
+        buf.extend_from_slice(&self.i_reg.to_le_bytes());
+        buf.push(self.dt);
+        buf.push(self.st);
+        buf.extend(self.keys.iter().map(|&key| key as u8));
+        buf.extend_from_slice(&(self.stack.sp as u32).to_le_bytes());
+        for &slot in &self.stack.data {
+            buf.extend_from_slice(&(slot as u32).to_le_bytes());
+        }
+        buf.extend(self.screen.pixels().iter().map(|&pixel| pixel as u8));
+
+        File::create(path)?.write_all(&buf)
+    }
+
+    /// Restore a complete machine state previously written by `save_state`.
+    /// Fails with `ErrorKind::InvalidData` if `path` isn't exactly
+    /// `STATE_SIZE` bytes, or if it decodes to a `pc`/`i_reg`/`sp` outside
+    /// `ram`/`stack.data`'s bounds, e.g. a stale, partial, or hand-edited
+    /// save file - rather than loading it and panicking on the next `tick`.
+    pub fn load_state(&mut self, path: &str) -> io::Result<()> {
+        let mut buf = Vec::new();
+        File::open(path)?.read_to_end(&mut buf)?;
+
+        if buf.len() != STATE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "state file {path} is {} bytes, expected {STATE_SIZE}",
+                    buf.len()
+                ),
+            ));
+        }
+
+        let mut cur = ByteCursor::new(&buf);
+        let v_reg = cur.take(NUM_REGS);
+        let ram = cur.take(RAM_SIZE);
+        let pc = cur.take_u32() as usize;
+        let i_reg = cur.take_u16();
+        let dt = cur.take_u8();
+        let st = cur.take_u8();
+        let keys = cur.take(NUM_KEYS);
+        let sp = cur.take_u32() as usize;
+        let mut stack = [0usize; STACK_SIZE];
+        for slot in stack.iter_mut() {
+            *slot = cur.take_u32() as usize;
+        }
+        let pixel_bytes = cur.take(Screen::PIXEL_COUNT);
+
+        // pc is read as a two-byte instruction, so it needs room for both bytes.
+        if pc >= RAM_SIZE - 1 || i_reg as usize >= RAM_SIZE || sp > STACK_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("state file {path} has an out-of-range pc/i_reg/sp"),
+            ));
+        }
+
+        self.v_reg.copy_from_slice(v_reg);
+        self.ram.copy_from_slice(ram);
+        self.pc = pc;
+        self.i_reg = i_reg;
+        self.dt = dt;
+        self.set_sound_timer(st);
+        for (slot, &byte) in self.keys.iter_mut().zip(keys) {
+            *slot = byte != 0;
+        }
+        self.stack.sp = sp;
+        self.stack.data = stack;
+
+        let mut pixels = [false; Screen::PIXEL_COUNT];
+        for (slot, &byte) in pixels.iter_mut().zip(pixel_bytes) {
+            *slot = byte != 0;
+        }
+        self.screen.restore_pixels(pixels);
+
+        Ok(())
+    }
+}
+
+/// Total byte length of a `save_state` dump, in field order: registers, RAM,
+/// pc, i_reg, dt, st, keys, stack pointer, stack slots, then screen pixels.
+/// `load_state` checks an input file against this before touching it, so a
+/// truncated or corrupt file is rejected instead of panicking `ByteCursor`.
+const STATE_SIZE: usize = NUM_REGS
+    + RAM_SIZE
+    + 4 // pc (u32)
+    + 2 // i_reg (u16)
+    + 1 // dt
+    + 1 // st
+    + NUM_KEYS
+    + 4 // stack.sp (u32)
+    + STACK_SIZE * 4 // stack.data (u32 each)
+    + Screen::PIXEL_COUNT;
+
+/// A read cursor over a flat byte buffer, used to parse the fixed-layout
+/// save-state format written by `CPU::save_state`.
+struct ByteCursor<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteCursor<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let slice = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        slice
+    }
+
+    fn take_u8(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    fn take_u16(&mut self) -> u16 {
+        u16::from_le_bytes(self.take(2).try_into().unwrap())
+    }
+
+    fn take_u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take(4).try_into().unwrap())
     }
 }
@@ -0,0 +1,143 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+};
+
+/// A single-step monitor command, parsed from a line of stdin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugCommand {
+    /// Execute the next `n` instructions, then prompt again.
+    Step(usize),
+    /// Stop prompting and run until the next breakpoint.
+    Continue,
+    /// Add a PC breakpoint.
+    SetBreakpoint(usize),
+    /// Dump `v_reg`, `i_reg`, `pc`, `dt`, `st` and the call stack.
+    DumpRegisters,
+    /// Hex-dump `len` bytes of RAM starting at `addr`.
+    DumpMemory(usize, usize),
+    /// Disassemble `count` instructions starting at `addr`.
+    Disassemble(usize, usize),
+    /// A line that didn't parse as any of the above.
+    Invalid(String),
+}
+
+/// Interactive single-step debugger state: whether execution is currently
+/// stopped, the set of PC breakpoints, and enough memory of the last
+/// command to support re-running it with a bare `Enter`.
+pub struct Debugger {
+    last_command: Option<String>,
+    repeat: usize,
+    breakpoints: HashSet<usize>,
+    stopped: bool,
+    steps_remaining: usize,
+}
+
+impl Debugger {
+    pub fn new() -> Self {
+        Self {
+            last_command: None,
+            repeat: 1,
+            breakpoints: HashSet::new(),
+            stopped: true,
+            steps_remaining: 0,
+        }
+    }
+
+    /// Whether execution should stop and prompt before running the
+    /// instruction at `pc`.
+    pub fn should_break(&self, pc: usize) -> bool {
+        self.stopped || self.breakpoints.contains(&pc)
+    }
+
+    pub fn add_breakpoint(&mut self, addr: usize) {
+        self.breakpoints.insert(addr);
+    }
+
+    /// Run `n` more instructions, then stop and prompt again.
+    pub fn step(&mut self, n: usize) {
+        self.steps_remaining = n;
+        self.stopped = false;
+    }
+
+    /// Run until the next breakpoint.
+    pub fn resume(&mut self) {
+        self.steps_remaining = 0;
+        self.stopped = false;
+    }
+
+    /// Call once per executed instruction while not stopped, to count down
+    /// an in-progress `step`.
+    pub fn tick(&mut self) {
+        if self.steps_remaining > 0 {
+            self.steps_remaining -= 1;
+            if self.steps_remaining == 0 {
+                self.stopped = true;
+            }
+        }
+    }
+
+    /// Block on stdin for the next command. An empty line re-runs the last
+    /// one, the classic monitor-prompt convention.
+    pub fn next_command(&mut self) -> DebugCommand {
+        print!("(dbg) ");
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            return DebugCommand::Continue;
+        }
+
+        let line = match line.trim() {
+            "" => self.last_command.clone().unwrap_or_default(),
+            line => {
+                self.last_command = Some(line.to_string());
+                line.to_string()
+            }
+        };
+
+        self.parse(&line)
+    }
+
+    fn parse(&mut self, line: &str) -> DebugCommand {
+        let mut parts = line.split_whitespace();
+        match parts.next() {
+            Some("s") | Some("step") => {
+                if let Some(n) = parts.next().and_then(|n| n.parse().ok()) {
+                    self.repeat = n;
+                }
+                DebugCommand::Step(self.repeat)
+            }
+            Some("c") | Some("continue") => DebugCommand::Continue,
+            Some("b") | Some("break") => match parts.next().and_then(parse_addr) {
+                Some(addr) => DebugCommand::SetBreakpoint(addr),
+                None => DebugCommand::Invalid(line.to_string()),
+            },
+            Some("r") | Some("regs") => DebugCommand::DumpRegisters,
+            Some("m") | Some("mem") => {
+                match (parts.next().and_then(parse_addr), parts.next().and_then(|n| n.parse().ok())) {
+                    (Some(addr), Some(len)) => DebugCommand::DumpMemory(addr, len),
+                    _ => DebugCommand::Invalid(line.to_string()),
+                }
+            }
+            Some("d") | Some("disasm") => {
+                match (parts.next().and_then(parse_addr), parts.next().and_then(|n| n.parse().ok())) {
+                    (Some(addr), Some(count)) => DebugCommand::Disassemble(addr, count),
+                    _ => DebugCommand::Invalid(line.to_string()),
+                }
+            }
+            _ => DebugCommand::Invalid(line.to_string()),
+        }
+    }
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Addresses are always typed in hex, with or without a leading "0x".
+fn parse_addr(arg: &str) -> Option<usize> {
+    usize::from_str_radix(arg.trim_start_matches("0x").trim_start_matches("0X"), 16).ok()
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+
+/// A decoded CHIP-8 instruction. Pure data - decoding never executes
+/// anything, so both `CPU::dispatch` and the debugger's disassembler can
+/// share this single source of truth for what an opcode means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    NoOp,
+    ClearScreen,
+    Return,
+    Jump(u16),
+    CallSubroutine(u16),
+    SkipEqImm(u8, u8),
+    SkipNeqImm(u8, u8),
+    SkipEqReg(u8, u8),
+    SetImm(u8, u8),
+    AddImm(u8, u8),
+    SetReg(u8, u8),
+    Or(u8, u8),
+    And(u8, u8),
+    Xor(u8, u8),
+    AddReg(u8, u8),
+    SubReg(u8, u8),
+    ShiftRight(u8, u8),
+    SubRegRev(u8, u8),
+    ShiftLeft(u8, u8),
+    SkipNeqReg(u8, u8),
+    SetIndex(u16),
+    JumpOffset(u8, u16),
+    Random(u8, u8),
+    Draw(u8, u8, u8),
+    SkipKeyPressed(u8),
+    SkipKeyNotPressed(u8),
+    GetDelay(u8),
+    WaitKey(u8),
+    SetDelay(u8),
+    SetSound(u8),
+    AddIndex(u8),
+    SetFont(u8),
+    StoreBcd(u8),
+    StoreRegs(u8),
+    LoadRegs(u8),
+    /// Any nibble pattern none of the above recognize.
+    Unknown(u8, u8),
+}
+
+/// Decode the two opcode bytes into an `Instruction`. Total - every byte
+/// pair maps to either a known instruction or `Unknown`, so this is safe
+/// to call on arbitrary memory (e.g. while disassembling data bytes).
+pub fn decode(high_byte: u8, low_byte: u8) -> Instruction {
+    let b3 = (high_byte & 0xF0) >> 4;
+    let b2 = high_byte & 0x0F;
+    let b1 = (low_byte & 0xF0) >> 4;
+    let b0 = low_byte & 0x0F;
+    let nn = low_byte;
+    let nnn = ((high_byte as u16 & 0x0F) << 8) | low_byte as u16;
+
+    use Instruction::*;
+    match b3 {
+        0 => match (b2, b1, b0) {
+            (0, 0xE, 0xE) => Return,
+            (0, 0xE, 0) => ClearScreen,
+            (0, 0, 0) => NoOp,
+            _ => Unknown(high_byte, low_byte),
+        },
+        1 => Jump(nnn),
+        2 => CallSubroutine(nnn),
+        3 => SkipEqImm(b2, nn),
+        4 => SkipNeqImm(b2, nn),
+        5 if b0 == 0 => SkipEqReg(b2, b1),
+        6 => SetImm(b2, nn),
+        7 => AddImm(b2, nn),
+        8 => match b0 {
+            0 => SetReg(b2, b1),
+            1 => Or(b2, b1),
+            2 => And(b2, b1),
+            3 => Xor(b2, b1),
+            4 => AddReg(b2, b1),
+            5 => SubReg(b2, b1),
+            6 => ShiftRight(b2, b1),
+            7 => SubRegRev(b2, b1),
+            0xE => ShiftLeft(b2, b1),
+            _ => Unknown(high_byte, low_byte),
+        },
+        9 if b0 == 0 => SkipNeqReg(b2, b1),
+        0xA => SetIndex(nnn),
+        0xB => JumpOffset(b2, nnn),
+        0xC => Random(b2, nn),
+        0xD => Draw(b2, b1, b0),
+        0xE => match (b1, b0) {
+            (9, 0xE) => SkipKeyPressed(b2),
+            (0xA, 1) => SkipKeyNotPressed(b2),
+            _ => Unknown(high_byte, low_byte),
+        },
+        0xF => match (b1, b0) {
+            (0, 7) => GetDelay(b2),
+            (0, 0xA) => WaitKey(b2),
+            (1, 5) => SetDelay(b2),
+            (1, 8) => SetSound(b2),
+            (1, 0xE) => AddIndex(b2),
+            (2, 9) => SetFont(b2),
+            (3, 3) => StoreBcd(b2),
+            (5, 5) => StoreRegs(b2),
+            (6, 5) => LoadRegs(b2),
+            _ => Unknown(high_byte, low_byte),
+        },
+        _ => Unknown(high_byte, low_byte),
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Instruction::NoOp => write!(f, "NOP"),
+            Instruction::ClearScreen => write!(f, "CLS"),
+            Instruction::Return => write!(f, "RET"),
+            Instruction::Jump(nnn) => write!(f, "JP {nnn:#05X}"),
+            Instruction::CallSubroutine(nnn) => write!(f, "CALL {nnn:#05X}"),
+            Instruction::SkipEqImm(x, nn) => write!(f, "SE V{x:X}, {nn:#04X}"),
+            Instruction::SkipNeqImm(x, nn) => write!(f, "SNE V{x:X}, {nn:#04X}"),
+            Instruction::SkipEqReg(x, y) => write!(f, "SE V{x:X}, V{y:X}"),
+            Instruction::SetImm(x, nn) => write!(f, "LD V{x:X}, {nn:#04X}"),
+            Instruction::AddImm(x, nn) => write!(f, "ADD V{x:X}, {nn:#04X}"),
+            Instruction::SetReg(x, y) => write!(f, "LD V{x:X}, V{y:X}"),
+            Instruction::Or(x, y) => write!(f, "OR V{x:X}, V{y:X}"),
+            Instruction::And(x, y) => write!(f, "AND V{x:X}, V{y:X}"),
+            Instruction::Xor(x, y) => write!(f, "XOR V{x:X}, V{y:X}"),
+            Instruction::AddReg(x, y) => write!(f, "ADD V{x:X}, V{y:X}"),
+            Instruction::SubReg(x, y) => write!(f, "SUB V{x:X}, V{y:X}"),
+            Instruction::ShiftRight(x, y) => write!(f, "SHR V{x:X}, V{y:X}"),
+            Instruction::SubRegRev(x, y) => write!(f, "SUBN V{x:X}, V{y:X}"),
+            Instruction::ShiftLeft(x, y) => write!(f, "SHL V{x:X}, V{y:X}"),
+            Instruction::SkipNeqReg(x, y) => write!(f, "SNE V{x:X}, V{y:X}"),
+            Instruction::SetIndex(nnn) => write!(f, "LD I, {nnn:#05X}"),
+            Instruction::JumpOffset(_, nnn) => write!(f, "JP V0, {nnn:#05X}"),
+            Instruction::Random(x, nn) => write!(f, "RND V{x:X}, {nn:#04X}"),
+            Instruction::Draw(x, y, n) => write!(f, "DRW V{x:X}, V{y:X}, {n:#X}"),
+            Instruction::SkipKeyPressed(x) => write!(f, "SKP V{x:X}"),
+            Instruction::SkipKeyNotPressed(x) => write!(f, "SKNP V{x:X}"),
+            Instruction::GetDelay(x) => write!(f, "LD V{x:X}, DT"),
+            Instruction::WaitKey(x) => write!(f, "LD V{x:X}, K"),
+            Instruction::SetDelay(x) => write!(f, "LD DT, V{x:X}"),
+            Instruction::SetSound(x) => write!(f, "LD ST, V{x:X}"),
+            Instruction::AddIndex(x) => write!(f, "ADD I, V{x:X}"),
+            Instruction::SetFont(x) => write!(f, "LD F, V{x:X}"),
+            Instruction::StoreBcd(x) => write!(f, "LD B, V{x:X}"),
+            Instruction::StoreRegs(x) => write!(f, "LD [I], V{x:X}"),
+            Instruction::LoadRegs(x) => write!(f, "LD V{x:X}, [I]"),
+            Instruction::Unknown(high, low) => write!(f, "DW {high:02X}{low:02X}"),
+        }
+    }
+}
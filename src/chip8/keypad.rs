@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode};
+
+pub const NUM_KEYS: usize = 16;
+
+pub type KeyState = [bool; NUM_KEYS];
+
+/// Emulator-level hotkeys that ride along the same input layer as the hex
+/// keypad, but are not part of the CHIP-8 key set itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hotkey {
+    SaveState,
+    LoadState,
+}
+
+/// A source of input for the emulator: the 16-key CHIP-8 hex keypad, plus
+/// the emulator's own save/load-state hotkeys riding along the same input
+/// stream. `CPU` polls this once per frame rather than per instruction, so
+/// an implementation only needs to answer "what's held right now?".
+pub trait Keypad {
+    /// Sample the current state of all 16 keys.
+    fn poll(&mut self) -> KeyState;
+
+    /// Return and clear any hotkey observed since the last call.
+    fn hotkey(&mut self) -> Option<Hotkey> {
+        None
+    }
+}
+
+/// Reports every key up, always. `-u` runs have no terminal to read from,
+/// so this stands in for `TerminalKeypad` to keep `CPU` ignorant of whether
+/// there's a real input device behind it.
+pub struct NullKeypad;
+
+impl Keypad for NullKeypad {
+    fn poll(&mut self) -> KeyState {
+        [false; NUM_KEYS]
+    }
+}
+
+/// Maps the standard 1234/QWER/ASDF/ZXCV layout onto the CHIP-8 hex keypad:
+///
+/// ```text
+/// 1 2 3 4      1 2 3 C
+/// Q W E R  ->  4 5 6 D
+/// A S D F      7 8 9 E
+/// Z X C V      A 0 B F
+/// ```
+///
+/// Terminals only report key-press events, never releases, so a key is
+/// considered held for a short window after its last press event.
+pub struct TerminalKeypad {
+    last_seen: HashMap<KeyCode, Instant>,
+    pending_hotkey: Option<Hotkey>,
+}
+
+impl TerminalKeypad {
+    const HOLD_WINDOW: Duration = Duration::from_millis(150);
+
+    // F5/F9 mirror the classic save-state/load-state hotkeys of console emulators.
+    const SAVE_STATE_KEY: KeyCode = KeyCode::F(5);
+    const LOAD_STATE_KEY: KeyCode = KeyCode::F(9);
+
+    const LAYOUT: [(KeyCode, usize); NUM_KEYS] = [
+        (KeyCode::Char('1'), 0x1),
+        (KeyCode::Char('2'), 0x2),
+        (KeyCode::Char('3'), 0x3),
+        (KeyCode::Char('4'), 0xC),
+        (KeyCode::Char('q'), 0x4),
+        (KeyCode::Char('w'), 0x5),
+        (KeyCode::Char('e'), 0x6),
+        (KeyCode::Char('r'), 0xD),
+        (KeyCode::Char('a'), 0x7),
+        (KeyCode::Char('s'), 0x8),
+        (KeyCode::Char('d'), 0x9),
+        (KeyCode::Char('f'), 0xE),
+        (KeyCode::Char('z'), 0xA),
+        (KeyCode::Char('x'), 0x0),
+        (KeyCode::Char('c'), 0xB),
+        (KeyCode::Char('v'), 0xF),
+    ];
+
+    pub fn new() -> Self {
+        Self {
+            last_seen: HashMap::new(),
+            pending_hotkey: None,
+        }
+    }
+}
+
+impl Default for TerminalKeypad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keypad for TerminalKeypad {
+    fn poll(&mut self) -> KeyState {
+        while event::poll(Duration::ZERO).unwrap_or(false) {
+            if let Ok(Event::Key(key)) = event::read() {
+                match key.code {
+                    Self::SAVE_STATE_KEY => self.pending_hotkey = Some(Hotkey::SaveState),
+                    Self::LOAD_STATE_KEY => self.pending_hotkey = Some(Hotkey::LoadState),
+                    _ => {}
+                }
+                if Self::LAYOUT.iter().any(|(code, _)| *code == key.code) {
+                    self.last_seen.insert(key.code, Instant::now());
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let mut keys = [false; NUM_KEYS];
+        for (code, hex) in Self::LAYOUT {
+            if let Some(pressed_at) = self.last_seen.get(&code) {
+                keys[hex] = now.duration_since(*pressed_at) < Self::HOLD_WINDOW;
+            }
+        }
+        keys
+    }
+
+    fn hotkey(&mut self) -> Option<Hotkey> {
+        self.pending_hotkey.take()
+    }
+}
@@ -0,0 +1,74 @@
+/// Selects how a handful of disputed opcodes behave, since real ROMs are
+/// written against whichever platform's interpretation they target and the
+/// well-known CHIP-8 implementations disagree on exactly these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quirks {
+    /// 8XY6/8XYE: shift VY into VX (false), or shift VX in place and ignore Y (true).
+    pub shift_in_place: bool,
+    /// BNNN: jump to NNN + V0 (false), or BXNN = NNN + VX (true).
+    pub jump_adds_vx: bool,
+    /// FX55/FX65: how far I advances after the load/store loop.
+    pub load_store_increment: IndexIncrement,
+    /// 8XY1/8XY2/8XY3: whether the logical ops additionally reset VF to 0.
+    pub logical_resets_vf: bool,
+}
+
+/// How FX55/FX65 leave the index register once the loop completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexIncrement {
+    /// I += X + 1, as the original COSMAC VIP interpreter did.
+    XPlusOne,
+    /// I is left unchanged, as SUPER-CHIP does.
+    Unchanged,
+    /// I += X.
+    X,
+}
+
+impl Quirks {
+    /// Original CHIP-8 (COSMAC VIP) behavior.
+    pub fn chip8() -> Self {
+        Self {
+            shift_in_place: false,
+            jump_adds_vx: false,
+            load_store_increment: IndexIncrement::XPlusOne,
+            logical_resets_vf: true,
+        }
+    }
+
+    /// SUPER-CHIP behavior.
+    pub fn superchip() -> Self {
+        Self {
+            shift_in_place: true,
+            jump_adds_vx: true,
+            load_store_increment: IndexIncrement::Unchanged,
+            logical_resets_vf: false,
+        }
+    }
+
+    /// XO-CHIP behavior.
+    pub fn xochip() -> Self {
+        Self {
+            shift_in_place: true,
+            jump_adds_vx: false,
+            load_store_increment: IndexIncrement::X,
+            logical_resets_vf: false,
+        }
+    }
+
+    /// Parse one of "chip8", "superchip" or "xochip" (case-insensitive),
+    /// as passed via the CLI.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "chip8" | "chip-8" => Some(Self::chip8()),
+            "superchip" | "schip" | "super-chip" => Some(Self::superchip()),
+            "xochip" | "xo-chip" => Some(Self::xochip()),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self::chip8()
+    }
+}
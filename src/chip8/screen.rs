@@ -12,6 +12,7 @@ pub struct Screen {
 impl Screen {
     const SCREEN_WIDTH: usize = 64;
     const SCREEN_HEIGHT: usize = 32;
+    pub(crate) const PIXEL_COUNT: usize = Self::SCREEN_WIDTH * Self::SCREEN_HEIGHT;
 
     pub fn new(renderer: Box<dyn Renderer>) -> Self {
         Self {
@@ -24,6 +25,16 @@ impl Screen {
         self.pixel.fill(false);
     }
 
+    /// The raw pixel buffer, for snapshotting. Excludes the renderer, which
+    /// is never part of a save state.
+    pub(crate) fn pixels(&self) -> &[bool; Self::PIXEL_COUNT] {
+        &self.pixel
+    }
+
+    pub(crate) fn restore_pixels(&mut self, pixel: [bool; Self::PIXEL_COUNT]) {
+        self.pixel = pixel;
+    }
+
     pub fn draw_sprite(&mut self, sprite: &[u8], pos_x: usize, pos_y: usize) -> bool {
         log::debug!("draw sprite at {}x{}: {:?}", pos_x, pos_y, sprite);
         let mut pixel_changed = false;
@@ -0,0 +1,112 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    Stream,
+};
+
+/// The CHIP-8 sound timer only has two states - ticking down, or not - so
+/// this is just an on/off gate: `CPU` calls `start`/`stop` as `st` crosses
+/// zero and leaves pitch, envelope, and everything else about the tone up
+/// to the implementation.
+pub trait Sound {
+    fn start(&mut self);
+    fn stop(&mut self);
+}
+
+/// Discards every `start`/`stop` call. `-u` runs have no audio device to
+/// open, so this stands in for `SquareWaveBeep` without `CPU` needing to
+/// know the difference.
+pub struct NullSound;
+
+impl Sound for NullSound {
+    fn start(&mut self) {}
+    fn stop(&mut self) {}
+}
+
+/// A continuously-running square-wave tone, gated on and off by the CHIP-8
+/// sound timer. The output stream runs for the lifetime of this struct and
+/// is never started from an empty buffer - only the envelope target
+/// changes - and the gate is ramped with a short linear attack/release
+/// envelope so toggling it doesn't click or ring.
+pub struct SquareWaveBeep {
+    gate: Arc<AtomicBool>,
+    _stream: Stream,
+}
+
+impl SquareWaveBeep {
+    const FREQUENCY_HZ: f32 = 440.0;
+    const ENVELOPE_SECONDS: f32 = 0.005;
+
+    pub fn new() -> Self {
+        let host = cpal::default_host();
+        let device = host
+            .default_output_device()
+            .expect("no default audio output device");
+        let config = device
+            .default_output_config()
+            .expect("no default audio output config");
+
+        let gate = Arc::new(AtomicBool::new(false));
+        let stream = Self::build_stream(&device, &config, Arc::clone(&gate));
+        stream.play().expect("failed to start audio stream");
+
+        Self {
+            gate,
+            _stream: stream,
+        }
+    }
+
+    fn build_stream(
+        device: &cpal::Device,
+        config: &cpal::SupportedStreamConfig,
+        gate: Arc<AtomicBool>,
+    ) -> Stream {
+        let sample_rate = config.sample_rate().0 as f32;
+        let channels = config.channels() as usize;
+        let envelope_step = 1.0 / (Self::ENVELOPE_SECONDS * sample_rate);
+
+        let mut phase = 0.0f32;
+        let mut amplitude = 0.0f32;
+
+        device
+            .build_output_stream(
+                &config.clone().into(),
+                move |data: &mut [f32], _| {
+                    for frame in data.chunks_mut(channels) {
+                        let target = if gate.load(Ordering::Relaxed) { 1.0 } else { 0.0 };
+                        amplitude += (target - amplitude).clamp(-envelope_step, envelope_step);
+
+                        phase = (phase + Self::FREQUENCY_HZ / sample_rate).fract();
+                        let sample = if phase < 0.5 { amplitude } else { -amplitude };
+
+                        for out in frame {
+                            *out = sample;
+                        }
+                    }
+                },
+                |err| log::debug!("audio stream error: {err}"),
+                None,
+            )
+            .expect("failed to build audio stream")
+    }
+}
+
+impl Default for SquareWaveBeep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sound for SquareWaveBeep {
+    fn start(&mut self) {
+        self.gate.store(true, Ordering::Relaxed);
+    }
+
+    fn stop(&mut self) {
+        self.gate.store(false, Ordering::Relaxed);
+    }
+}
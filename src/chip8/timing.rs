@@ -0,0 +1,36 @@
+/// How fast the emulator runs: how many instructions execute per second,
+/// and how often the screen (and the delay/sound timers) are updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub clock_hz: u32,
+    pub fps: u32,
+}
+
+impl Timing {
+    /// `fps` is floored to 1: it divides a frame duration and indexes
+    /// `ticks_per_frame`'s own division, so 0 would make `CPU::run` panic
+    /// trying to build a `Duration` from an infinite number of seconds.
+    pub fn new(clock_hz: u32, fps: u32) -> Self {
+        Self {
+            clock_hz,
+            fps: fps.max(1),
+        }
+    }
+
+    /// Instructions to run per frame to hold `clock_hz` at this frame rate.
+    pub fn ticks_per_frame(&self) -> usize {
+        ((self.clock_hz as f64 / self.fps as f64).round() as usize).max(1)
+    }
+}
+
+impl Default for Timing {
+    /// ~540 instructions/sec at 60 fps - a common default among CHIP-8
+    /// interpreters that keeps most ROMs feeling the speed they were
+    /// authored for.
+    fn default() -> Self {
+        Self {
+            clock_hz: 540,
+            fps: 60,
+        }
+    }
+}
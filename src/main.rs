@@ -1,20 +1,35 @@
-use std::{collections::HashSet, process::exit};
+use std::{collections::HashSet, path::Path, process::exit};
 
 use after8::chip8::{
     cpu::CPU,
+    keypad::{Keypad, NullKeypad, TerminalKeypad},
+    quirks::Quirks,
     screen::{ConsoleRenderer, Renderer, Screen, VoidRenderer},
+    sound::{NullSound, Sound, SquareWaveBeep},
+    timing::Timing,
 };
 use log::{Metadata, Record};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
     if args.len() < 2 {
-        println!("usage: {} -u -v <rom file>", args[0]);
+        println!(
+            "usage: {} -u -v -d --quirks <chip8|superchip|xochip> --clock <hz> --fps <fps> -s <state file> <rom file>",
+            args[0]
+        );
         exit(1);
     }
 
     let params: HashSet<String> = HashSet::from_iter(args.clone());
 
+    let quirks = match quirks_arg(&args) {
+        Some(name) => Quirks::from_name(&name).unwrap_or_else(|| {
+            eprintln!("unknown quirks preset '{name}', falling back to chip8");
+            Quirks::chip8()
+        }),
+        None => Quirks::chip8(),
+    };
+
     let verbose = params.contains("-v");
     let log_level = if verbose {
         log::LevelFilter::Debug
@@ -33,12 +48,58 @@ fn main() {
     };
     let screen = Screen::new(renderer);
 
+    let keypad: Box<dyn Keypad> = if no_ui {
+        Box::new(NullKeypad)
+    } else {
+        Box::new(TerminalKeypad::new())
+    };
+
+    let sound: Box<dyn Sound> = if no_ui {
+        Box::new(NullSound)
+    } else {
+        Box::new(SquareWaveBeep::new())
+    };
+
     let file_name = args.last().unwrap();
-    let mut cpu = CPU::with_rom(screen, file_name);
+    let mut cpu = CPU::with_rom(screen, keypad, quirks, sound, file_name);
+
+    if let Some(state_path) = value_arg(&args, "-s") {
+        if Path::new(&state_path).exists() {
+            if let Err(err) = cpu.load_state(&state_path) {
+                log::warn!("failed to load state from {state_path}: {err}");
+            }
+        }
+        cpu.set_state_path(state_path);
+    }
+
+    if params.contains("-d") {
+        cpu.enable_debugger();
+    }
+
+    let default_timing = Timing::default();
+    let clock_hz = value_arg(&args, "--clock")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default_timing.clock_hz);
+    let fps = value_arg(&args, "--fps")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default_timing.fps);
+    cpu.set_timing(Timing::new(clock_hz, fps));
+
     //cpu.run_n_ticks(200);
     cpu.run();
 }
 
+fn quirks_arg(args: &[String]) -> Option<String> {
+    value_arg(args, "--quirks")
+}
+
+fn value_arg(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|idx| args.get(idx + 1))
+        .cloned()
+}
+
 struct SimpleLogger;
 
 impl log::Log for SimpleLogger {